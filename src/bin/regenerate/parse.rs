@@ -1,83 +1,54 @@
-use super::{Tag, TagSet, TagsBuilder};
+use super::{InflectionKind, LanguageCode, Tag, TagSet, TagsBuilder};
 use fst::Map;
 use regex::Regex;
-use ustr::{ustr, UstrMap};
+use std::collections::HashMap;
+use ustr::{ustr, Ustr, UstrMap};
 
-const TAG_ALIASES: &[(&Tag, &[&str])] = &[
-    (
-        &Tag::Adjective,
-        &[
-            "en-adj",
-            "en-adjective",
-            "en|head|adj",
-            "en|head|adjective",
-            "head|en|adjective",
-        ],
-    ),
+// English "form of" templates whose first positional argument names the
+// lemma, e.g. `{{en-past of|run}}` -> ("run", PastTense).
+const INFLECTION_ALIASES: &[(&str, InflectionKind)] = &[
+    ("en-past of", InflectionKind::PastTense),
     (
-        &Tag::Adverb,
-        &[
-            "en-adv",
-            "en-adverb",
-            "en|head|adv",
-            "en|head|adverb",
-            "head|en|adverb",
-        ],
+        "en-third-person singular of",
+        InflectionKind::ThirdPersonSingular,
     ),
+    ("en-comparative of", InflectionKind::Comparative),
+    ("en-superlative of", InflectionKind::Superlative),
+    ("en-irregular plural of", InflectionKind::IrregularPlural),
+];
+
+// POS suffixes, independent of the leading language subtag. The same
+// suffix (e.g. "noun") is shared by every language's `xx-noun` template
+// and by the generic `head|xx|noun` form.
+const TAG_ALIASES: &[(&Tag, &[&str])] = &[
+    (&Tag::Adjective, &["adj", "adjective"]),
+    (&Tag::Adverb, &["adv", "adverb"]),
     (
         &Tag::Conjunction,
-        &[
-            "en-con",
-            "en-conj",
-            "en-conjunction",
-            "en-conj-simple",
-            "en|head|con",
-            "en|head|conj",
-            "en|head|conjunction",
-        ],
-    ),
-    (
-        &Tag::Determiner,
-        &["en-det", "en|head|det", "head|en|determiner"],
-    ),
-    (
-        &Tag::Interjection,
-        &[
-            "en-interj",
-            "en-interjection",
-            "en-intj",
-            "en|head|interj",
-            "en|head|interjection",
-            "head|en|interjection",
-        ],
-    ),
-    (
-        &Tag::Noun,
-        &[
-            "en-noun",
-            "en|head|noun",
-            "head|en|noun",
-            "head|en|noun form",
-            "en-plural noun",
-        ],
-    ),
-    (&Tag::Numeral, &["en-num", "en|head|num"]),
-    (&Tag::Particle, &["en-part", "en|head|part"]),
-    (&Tag::Postposition, &["en-postp", "en|head|postp"]),
-    (&Tag::Preposition, &["en-prep", "en|head|prep"]),
-    (&Tag::Pronoun, &["en-pron", "en|head|pron"]),
-    (&Tag::ProperNoun, &["en-proper noun", "en|head|proper noun"]),
-    (
-        &Tag::Verb,
-        &["en-verb", "head|en|verb", "head|en|verb form"],
+        &["con", "conj", "conjunction", "conj-simple"],
     ),
+    (&Tag::Determiner, &["det", "determiner"]),
+    (&Tag::Interjection, &["interj", "interjection", "intj"]),
+    (&Tag::Noun, &["noun", "noun form", "plural noun"]),
+    (&Tag::Numeral, &["num"]),
+    (&Tag::Particle, &["part"]),
+    (&Tag::Postposition, &["postp"]),
+    (&Tag::Preposition, &["prep"]),
+    (&Tag::Pronoun, &["pron"]),
+    (&Tag::ProperNoun, &["proper noun"]),
+    (&Tag::Verb, &["verb", "verb form"]),
 ];
 
 pub struct ParserRegexes {
     tag_regex: Regex,
+    inflection_regex: Regex,
+    translation_regex: Regex,
+    gloss_template_regex: Regex,
+    gloss_link_regex: Regex,
     title_regex: Regex,
     opening_text_regex: Regex,
     pub alias_lookup: Map<Vec<u8>>,
+    tag_for_suffix: HashMap<String, Tag>,
 }
 
 impl std::default::Default for ParserRegexes {
@@ -90,20 +61,67 @@ impl std::default::Default for ParserRegexes {
             .collect();
 
         tag_aliases.sort_by(|(alias1, _), (alias2, _)| alias1.cmp(alias2));
+        tag_aliases.dedup_by(|(alias1, _), (alias2, _)| alias1 == alias2);
 
         for (alias, tag) in tag_aliases {
             tags_builder.insert_tag(alias, tag);
         }
 
+        let tag_for_suffix: HashMap<String, Tag> = TAG_ALIASES
+            .iter()
+            .flat_map(|(tag, aliases)| aliases.iter().map(move |alias| (alias.to_string(), **tag)))
+            .collect();
+
         ParserRegexes {
             alias_lookup: Map::new(tags_builder.into_inner()).unwrap(),
+            tag_for_suffix,
             tag_regex: Regex::new(
                 r#"(?x)
                     \{\{\s*
-                    ((?:en\-|head\|en\|)[^\|{}\d\.&]+)
+                    (?:
+                        ([a-zA-Z]{2,3}) - ([^\|{}\d\.&]+)
+                      |
+                        head \| ([a-zA-Z]{2,3}) \| ([^\|{}\d\.&]+)
+                    )
+                "#,
+            )
+            .unwrap(),
+            inflection_regex: Regex::new(
+                r#"(?x)
+                    \{\{\s*
+                    (en-(?:
+                        past\ of
+                      | third-person\ singular\ of
+                      | comparative\ of
+                      | superlative\ of
+                      | irregular\ plural\ of
+                    ))
+                    \s*\|\s*
+                    ([^\|{}]+)
+                "#,
+            )
+            .unwrap(),
+            // `{{t|es|amigo}}` / `{{t+|es|amigo|m}}` translation-table
+            // entries, found inside `{{trans-top}}`/`{{trans-bottom}}`
+            // sections. We don't track those section boundaries, same as
+            // `tag_regex` doesn't track which POS heading it's under.
+            translation_regex: Regex::new(
+                r#"(?x)
+                    \{\{\s*
+                    t\+?
+                    \s*\|\s*
+                    ([a-zA-Z-]+)
+                    \s*\|\s*
+                    ([^\|{}]+)
                 "#,
             )
             .unwrap(),
+            // Templates inside a gloss line, e.g. `{{lb|en|transitive}}`,
+            // stripped entirely rather than rendered to plain text.
+            gloss_template_regex: Regex::new(r#"\{\{[^{}]*\}\}"#).unwrap(),
+            // `[[target]]` / `[[target|display]]` wiki links, replaced with
+            // their display text (or `target` when there is no `|`).
+            gloss_link_regex: Regex::new(r#"\[\[([^\|\]]+)(?:\|([^\]]+))?\]\]"#).unwrap(),
             title_regex: Regex::new(
                 r#"(?x)
                     <title>
@@ -119,7 +137,18 @@ impl std::default::Default for ParserRegexes {
 #[derive(Debug)]
 pub struct PageInfo {
     pub title: String,
-    pub tags: TagSet,
+    /// Tags found on this page, grouped by the BCP-47 language of the
+    /// template they came from (e.g. `en`, `fr`).
+    pub tags: HashMap<LanguageCode, TagSet>,
+    /// "Form of" back-references found on this page, e.g. a page titled
+    /// "ran" carrying `[("run", PastTense)]`.
+    pub inflections: Vec<(Ustr, InflectionKind)>,
+    /// Translation-table entries found on this page, e.g. a page titled
+    /// "dog" carrying `[(es, "perro")]`.
+    pub translations: Vec<(LanguageCode, String)>,
+    /// Sense glosses found under each English POS template, in the order
+    /// the templates appear on the page.
+    pub glosses: Vec<(Tag, Vec<String>)>,
 }
 
 pub fn parse_page(
@@ -133,25 +162,217 @@ pub fn parse_page(
         .captures(&page_contents)
         .ok_or_else(|| format!("Failed to find title for page"))
         .map(|title| {
-            let mut tags = TagSet::default();
+            let mut tags = HashMap::new();
+            let mut inflections = Vec::new();
+            let mut translations = Vec::new();
+            let mut pos_template_spans = Vec::new();
             if let Some(m) = regexes.opening_text_regex.find(&page_contents) {
-                for wiki_tag in regexes
-                    .tag_regex
-                    .captures_iter(&page_contents[m.end()..])
-                    .map(|cap| {
-                        let handle = ustr(&cap[1].trim());
-                        *tag_counter.entry(handle).or_default() += 1;
-                        handle
-                    })
-                {
-                    if let Some(existing_tag_mask) = regexes.alias_lookup.get(wiki_tag.as_str()) {
-                        tags.insert_tag_mask(existing_tag_mask as u32);
+                let text = &page_contents[m.end()..];
+
+                for cap in regexes.tag_regex.captures_iter(text) {
+                    let (lang, suffix) = match (cap.get(1), cap.get(2), cap.get(3), cap.get(4)) {
+                        (Some(lang), Some(suffix), _, _) => (lang.as_str(), suffix.as_str()),
+                        (_, _, Some(lang), Some(suffix)) => (lang.as_str(), suffix.as_str()),
+                        _ => continue,
+                    };
+
+                    let handle = ustr(&format!("{}-{}", lang, suffix.trim()));
+                    *tag_counter.entry(handle).or_default() += 1;
+
+                    if let Some((lang, tag)) = resolve_tag(regexes, lang, suffix) {
+                        tags.entry(lang)
+                            .or_insert_with(TagSet::default)
+                            .insert_tag_mask(tag);
+                    }
+
+                    let full_match = cap.get(0).unwrap();
+                    let template_lang = LanguageCode::parse(lang).ok();
+                    let tag = regexes.tag_for_suffix.get(suffix.trim()).copied();
+                    pos_template_spans.push((
+                        full_match.start(),
+                        full_match.end(),
+                        template_lang,
+                        tag,
+                    ));
+                }
+
+                let glosses = collect_glosses(regexes, text, &pos_template_spans);
+
+                for cap in regexes.inflection_regex.captures_iter(text) {
+                    let template = &cap[1];
+                    let lemma = ustr(cap[2].trim());
+
+                    if let Some((_, kind)) = INFLECTION_ALIASES
+                        .iter()
+                        .find(|(alias, _)| *alias == template)
+                    {
+                        inflections.push((lemma, *kind));
                     }
                 }
+
+                // Translation tables are a convention of enwiktionary's
+                // English entries (translating the English headword into
+                // other languages), so only keep hits found under the
+                // page's `==English==` section.
+                for cap in regexes.translation_regex.captures_iter(text) {
+                    let full_match = cap.get(0).unwrap();
+                    let term = cap[2].trim();
+
+                    if let Ok(lang) = LanguageCode::parse(&cap[1]) {
+                        if current_section_is_english(&pos_template_spans, full_match.start()) {
+                            translations.push((lang, term.to_string()));
+                        }
+                    }
+                }
+
                 add_to.push(PageInfo {
                     title: String::from(&title[1]),
                     tags,
+                    inflections,
+                    translations,
+                    glosses,
                 });
             }
         })
 }
+
+/// Resolves `suffix` to a `Tag` mask via `alias_lookup`, then pairs it with
+/// `lang` parsed as a BCP-47 language tag. Rejection of garbage suffixes
+/// like the unterminated `en-verb))` templates seen in the tag counter
+/// happens here, in the `alias_lookup` lookup -- `LanguageCode::parse`
+/// itself doesn't reject them, since `lang` is already constrained by
+/// `tag_regex` to 2-3 letters and `oxilangtag::LanguageTag::parse` never
+/// fails for a string that short.
+fn resolve_tag(regexes: &ParserRegexes, lang: &str, suffix: &str) -> Option<(LanguageCode, u32)> {
+    let lang = LanguageCode::parse(lang).ok()?;
+    let mask = regexes.alias_lookup.get(suffix.trim())? as u32;
+    Some((lang, mask))
+}
+
+/// Whether the POS template span immediately preceding `position` (i.e.
+/// the section `position` falls under) is an English (`en`) template.
+/// Returns `false` when no span precedes `position`.
+fn current_section_is_english(
+    pos_template_spans: &[(usize, usize, Option<LanguageCode>, Option<Tag>)],
+    position: usize,
+) -> bool {
+    pos_template_spans
+        .iter()
+        .rev()
+        .find(|&&(start, _, _, _)| start <= position)
+        .and_then(|(_, _, lang, _)| lang.as_ref())
+        .is_some_and(|lang| lang.as_str() == "en")
+}
+
+/// For each English-language POS template span with a resolved `Tag`,
+/// collects the `# …` definition lines between it and the next template
+/// (or the next `==` heading, whichever comes first). Every span, English
+/// or not, still bounds the region its successor covers, so a following
+/// `fr-noun` template correctly ends an English gloss region.
+fn collect_glosses(
+    regexes: &ParserRegexes,
+    text: &str,
+    pos_template_spans: &[(usize, usize, Option<LanguageCode>, Option<Tag>)],
+) -> Vec<(Tag, Vec<String>)> {
+    let mut glosses = Vec::new();
+
+    for (i, &(_, end, ref lang, tag)) in pos_template_spans.iter().enumerate() {
+        let Some(tag) = tag else { continue };
+        if !lang.as_ref().is_some_and(|lang| lang.as_str() == "en") {
+            continue;
+        }
+
+        let region_end = pos_template_spans
+            .get(i + 1)
+            .map(|&(start, _, _, _)| start)
+            .unwrap_or(text.len());
+        let region = &text[end..region_end];
+        let region = region.split("\n==").next().unwrap_or(region);
+
+        let senses: Vec<String> = region
+            .split('\n')
+            .filter_map(|line| {
+                let line = line.trim();
+                let rest = line.strip_prefix('#')?;
+                if rest.starts_with(['#', ':', '*']) {
+                    return None;
+                }
+                let gloss = clean_gloss_text(regexes, rest);
+                (!gloss.is_empty()).then_some(gloss)
+            })
+            .collect();
+
+        if !senses.is_empty() {
+            glosses.push((tag, senses));
+        }
+    }
+
+    glosses
+}
+
+/// Strips a gloss line down to plain text: templates are dropped entirely,
+/// wiki links are replaced with their display text, and bold/italic
+/// markers are removed.
+fn clean_gloss_text(regexes: &ParserRegexes, line: &str) -> String {
+    let line = regexes.gloss_template_regex.replace_all(line, "");
+    let line = regexes
+        .gloss_link_regex
+        .replace_all(&line, |caps: &regex::Captures| {
+            caps.get(2)
+                .or_else(|| caps.get(1))
+                .unwrap()
+                .as_str()
+                .to_string()
+        });
+
+    line.replace("'''", "").replace("''", "").trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MULTI_LANGUAGE_PAGE: &str = r#"<title>bank</title>
+<text>
+==English==
+===Noun===
+{{en-noun}}
+# a financial institution
+
+====Translations====
+{{trans-top}}
+* Spanish: {{t|es|banco}}
+{{trans-bottom}}
+
+==French==
+===Nom===
+{{fr-noun}}
+# rive
+
+====Traductions====
+{{trans-top}}
+* German: {{t|de|Ufer}}
+{{trans-bottom}}
+</text>
+"#;
+
+    #[test]
+    fn scopes_translations_and_glosses_to_the_english_section() {
+        let regexes = ParserRegexes::default();
+        let mut tag_counter = UstrMap::default();
+        let mut pages = Vec::new();
+
+        parse_page(&regexes, &mut tag_counter, &mut pages, MULTI_LANGUAGE_PAGE).unwrap();
+
+        let page = &pages[0];
+
+        assert_eq!(
+            page.translations,
+            vec![(LanguageCode::parse("es").unwrap(), "banco".to_string())]
+        );
+        assert_eq!(
+            page.glosses,
+            vec![(Tag::Noun, vec!["a financial institution".to_string()])]
+        );
+    }
+}
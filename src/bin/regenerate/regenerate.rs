@@ -1,5 +1,14 @@
 use parse::PageInfo;
-use std::{collections::HashMap, env, path::PathBuf, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Mutex,
+    },
+    time::Instant,
+};
 use ustr::UstrMap;
 
 static OPENING_PAGE: &str = "<page>";
@@ -7,7 +16,10 @@ static CLOSING_PAGE: &str = "</page>";
 
 mod parse;
 
-use wiktionary_part_of_speech_extract::{Tag, TagSet, TagsBuilder};
+use wiktionary_part_of_speech_extract::{
+    serialize_relations, GlossBuilder, InflectionKind, LanguageCode, LemmaBuilder, Tag, TagSet,
+    TagsBuilder, TranslationBuilder,
+};
 
 #[derive(Debug)]
 enum MyError {
@@ -15,6 +27,17 @@ enum MyError {
     InvalidPage(String),
 }
 
+impl std::fmt::Display for MyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MyError::Io(err) => write!(f, "{}", err),
+            MyError::InvalidPage(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for MyError {}
+
 impl From<std::io::Error> for MyError {
     fn from(err: std::io::Error) -> Self {
         MyError::Io(err)
@@ -27,74 +50,320 @@ impl From<String> for MyError {
     }
 }
 
+/// Wraps a reader so the splitter thread's actual progress through the
+/// file on disk (compressed or not) can be tracked independently of the
+/// decompressed bytes it ends up reading lines from.
+struct CountingReader<R> {
+    inner: R,
+    bytes_read: std::sync::Arc<AtomicU64>,
+}
+
+impl<R: std::io::Read> std::io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes_read.fetch_add(n as u64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
+/// Transparently decompresses `.bz2`/`.gz` dumps by file extension so
+/// callers can point this at `enwiktionary-*-pages-articles.xml.bz2`
+/// directly instead of piping through `bzcat` first.
+fn open_dump(
+    path: &str,
+    file: std::fs::File,
+    bytes_read: std::sync::Arc<AtomicU64>,
+) -> Box<dyn std::io::Read + Send> {
+    let counting = CountingReader {
+        inner: file,
+        bytes_read,
+    };
+
+    if path.ends_with(".bz2") {
+        Box::new(bzip2::read::BzDecoder::new(counting))
+    } else if path.ends_with(".gz") {
+        Box::new(flate2::read::GzDecoder::new(counting))
+    } else {
+        Box::new(counting)
+    }
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     use parse::ParserRegexes;
     use std::fs::File;
     use std::io::{BufRead, BufReader};
 
-    let parser_regexes = ParserRegexes::default();
-    let mut tag_counter = UstrMap::default();
+    let worker_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+
+    let mut tag_counter: UstrMap<usize> = UstrMap::default();
     let mut pages = Vec::new();
+    let mut parse_failures = 0usize;
     // Prints each argument on a separate line
     for file_to_parse in env::args().skip(1) {
         eprintln!("{}", file_to_parse);
 
-        let file = File::open(file_to_parse)?;
-        let total_bytes = file.metadata().unwrap().len();
-        let buffer = BufReader::new(file);
-        let mut page = String::new();
-        let mut is_inside_page = false;
+        let file = File::open(&file_to_parse)?;
+        let total_bytes = file.metadata()?.len();
+        let bytes_read = std::sync::Arc::new(AtomicU64::new(0));
+        let buffer = BufReader::new(open_dump(&file_to_parse, file, bytes_read.clone()));
 
-        let mut time_since_last_report = Instant::now();
-        let mut total_bytes_seen = 0;
-        let mut report_percentage_after = 0f64;
+        // Bounded so the splitter applies backpressure instead of buffering
+        // the whole dump's worth of pages in memory ahead of the workers.
+        let (page_tx, page_rx) = mpsc::sync_channel::<String>(worker_count * 4);
+        let page_rx = Mutex::new(page_rx);
 
-        for line in buffer.lines() {
-            let line = line?;
-            total_bytes_seen += line.len();
+        std::thread::scope(|scope| -> Result<(), MyError> {
+            let worker_handles: Vec<_> = (0..worker_count)
+                .map(|_| {
+                    let page_rx = &page_rx;
+                    scope.spawn(move || {
+                        let parser_regexes = ParserRegexes::default();
+                        let mut worker_tag_counter = UstrMap::default();
+                        let mut worker_pages = Vec::new();
+                        let mut worker_failures = 0usize;
 
-            if !is_inside_page && line.contains(OPENING_PAGE) {
-                is_inside_page = true;
-            } else {
-                if line.contains(CLOSING_PAGE) {
-                    parse::parse_page(&parser_regexes, &mut tag_counter, &mut pages, &page)?;
+                        while let Ok(page) = page_rx.lock().unwrap().recv() {
+                            if parse::parse_page(
+                                &parser_regexes,
+                                &mut worker_tag_counter,
+                                &mut worker_pages,
+                                &page,
+                            )
+                            .is_err()
+                            {
+                                worker_failures += 1;
+                            }
+                        }
 
-                    page.clear();
+                        (worker_tag_counter, worker_pages, worker_failures)
+                    })
+                })
+                .collect();
 
+            let mut page = String::new();
+            let mut is_inside_page = false;
+
+            let mut time_since_last_report = Instant::now();
+            let mut report_percentage_after = 0f64;
+
+            for line in buffer.lines() {
+                let line = line?;
+
+                if !is_inside_page && line.contains(OPENING_PAGE) {
+                    is_inside_page = true;
+                } else if line.contains(CLOSING_PAGE) {
+                    page_tx.send(std::mem::take(&mut page)).ok();
                     is_inside_page = false;
-                } else {
+                } else if is_inside_page {
                     page.push_str(&line);
                     page.push('\n');
                 }
+
+                let percentage_seen =
+                    bytes_read.load(Ordering::Relaxed) as f64 / total_bytes as f64;
+                if percentage_seen > report_percentage_after {
+                    let current_instant = Instant::now();
+                    eprintln!(
+                        "{}% complete in {:?}",
+                        (report_percentage_after * 100f64).round(),
+                        current_instant.duration_since(time_since_last_report)
+                    );
+                    report_percentage_after += 0.05;
+                    time_since_last_report = current_instant;
+                }
             }
 
-            let percentage_seen = (total_bytes_seen as f64) / (total_bytes as f64);
-            if percentage_seen > report_percentage_after {
-                let current_instant = Instant::now();
-                eprintln!(
-                    "{}% complete in {:?}",
-                    (report_percentage_after * 100f64).round(),
-                    current_instant.duration_since(time_since_last_report.clone())
-                );
-                report_percentage_after += 0.05;
-                time_since_last_report = current_instant;
+            // Dropping `page_tx`, the only sender, lets the workers'
+            // `recv()` calls return `Err` and exit their loops.
+            drop(page_tx);
+
+            for handle in worker_handles {
+                let (worker_tag_counter, worker_pages, worker_failures) =
+                    handle.join().expect("worker thread panicked");
+
+                for (tag, count) in worker_tag_counter {
+                    *tag_counter.entry(tag).or_default() += count;
+                }
+                pages.extend(worker_pages);
+                parse_failures += worker_failures;
             }
-        }
+
+            Ok(())
+        })?;
     }
 
     eprintln!("{:#?}", pages.len());
     eprintln!("{:#?}", tag_counter);
+    eprintln!("{} page(s) failed to parse", parse_failures);
+
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").unwrap_or(".".into()));
 
-    let fst_path = std::path::Path::new(&std::env::var_os("OUT_DIR").unwrap_or(".".into()))
-        .join("enwiktionary-word-tags.fst");
+    // Opt-in, same convention as `TRANSLATION_LANG` below: a comma-separated
+    // `WORD_TAG_LANGUAGES=en,fr` restricts which languages' word-tag FSTs get
+    // built, rather than one per language seen in the dump.
+    let languages = std::env::var("WORD_TAG_LANGUAGES").ok().map(|value| {
+        value
+            .split(',')
+            .filter_map(|lang| LanguageCode::parse(lang.trim()).ok())
+            .collect()
+    });
 
     build_fst_from_pages(
         pages.as_slice(),
         FSTOptions {
             exclude_pages_which_have_only_nouns: true,
             flatten_unicode: true,
+            languages,
         },
-        fst_path,
+        out_dir.clone(),
+    )?;
+
+    build_lemma_fst_from_pages(pages.as_slice(), out_dir.clone())?;
+
+    // Opt-in: the AWK-dump convention of selecting a single target language
+    // at generation time, via `TRANSLATION_LANG=es`, rather than a CLI flag.
+    if let Some(target_language) = std::env::var("TRANSLATION_LANG")
+        .ok()
+        .and_then(|lang| LanguageCode::parse(&lang).ok())
+    {
+        build_translation_fst(
+            pages.as_slice(),
+            TranslationOptions {
+                target_language,
+                strip_wiki_links: true,
+            },
+            out_dir,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Writes `en-lemmas.fst` (inflected surface form -> encoded relations
+/// group), the `en-lemmas.txt` lemma string table, and the
+/// `en-lemmas-relations.bin` relations table it indexes into. A surface
+/// form keeps every relation attested for it, since a single page can
+/// carry more than one inflection template (e.g. "leaves" as both the
+/// plural of "leaf" and the third-person singular of "leave").
+fn build_lemma_fst_from_pages(
+    pages: &[PageInfo],
+    out_dir: PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut inflections_by_form = HashMap::<String, Vec<(ustr::Ustr, InflectionKind)>>::new();
+
+    for info in pages {
+        for (lemma, kind) in &info.inflections {
+            inflections_by_form
+                .entry(info.title.to_lowercase())
+                .or_default()
+                .push((*lemma, *kind));
+        }
+    }
+
+    let mut forms_sorted = inflections_by_form.into_iter().collect::<Vec<_>>();
+    forms_sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let w = std::io::BufWriter::new(std::fs::File::create(out_dir.join("en-lemmas.fst"))?);
+    let mut lb = LemmaBuilder::new(w)?;
+
+    for (surface_form, relations) in &forms_sorted {
+        lb.insert(surface_form, relations)?;
+    }
+
+    let (lemmas, relations) = lb.finish()?;
+    std::fs::write(
+        out_dir.join("en-lemmas.txt"),
+        lemmas
+            .iter()
+            .map(|lemma| lemma.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )?;
+    std::fs::write(
+        out_dir.join("en-lemmas-relations.bin"),
+        serialize_relations(&relations),
+    )?;
+
+    Ok(())
+}
+
+pub struct TranslationOptions {
+    /// Emit only translations into this target language.
+    pub target_language: LanguageCode,
+    /// Strip `[[wiki link]]` markup from foreign terms, e.g. `[[perro]]`
+    /// -> `perro` and `[[can|perro]]` -> `perro`, before storing them.
+    pub strip_wiki_links: bool,
+}
+
+/// Replaces `[[target]]` and `[[target|display]]` wiki links with their
+/// display text (or `target` when there is no `|`), leaving everything
+/// else untouched.
+fn strip_wiki_link_markup(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("[[") {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find("]]") else {
+            out.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+
+        let link = &rest[start + 2..start + end];
+        out.push_str(link.rsplit('|').next().unwrap_or(link));
+        rest = &rest[start + end + 2..];
+    }
+    out.push_str(rest);
+
+    out
+}
+
+/// Writes `en-<lang>-translations.fst` (English headword -> foreign term(s))
+/// and the `en-<lang>-translations.txt` term table it indexes into.
+fn build_translation_fst(
+    pages: &[PageInfo],
+    options: TranslationOptions,
+    out_dir: PathBuf,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut terms_by_headword = HashMap::<String, Vec<String>>::new();
+
+    for info in pages {
+        for (lang, term) in &info.translations {
+            if *lang != options.target_language {
+                continue;
+            }
+
+            let term = if options.strip_wiki_links {
+                strip_wiki_link_markup(term)
+            } else {
+                term.clone()
+            };
+
+            terms_by_headword
+                .entry(info.title.to_lowercase())
+                .or_default()
+                .push(term);
+        }
+    }
+
+    let mut headwords_sorted = terms_by_headword.into_iter().collect::<Vec<_>>();
+    headwords_sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let fst_path = out_dir.join(format!("en-{}-translations.fst", options.target_language));
+    let w = std::io::BufWriter::new(std::fs::File::create(fst_path)?);
+    let mut tb = TranslationBuilder::new(w)?;
+
+    for (headword, terms) in headwords_sorted {
+        tb.insert(&headword, &terms)?;
+    }
+
+    let terms = tb.finish()?;
+    std::fs::write(
+        out_dir.join(format!("en-{}-translations.txt", options.target_language)),
+        terms.join("\n"),
     )?;
 
     Ok(())
@@ -109,16 +378,17 @@ pub struct FSTOptions {
     /// Anything that is a Noun & ...X, will include tags for Noun & ...X.
     /// For example, we will still include Noun tag on something that is Adjective and Noun.
     pub exclude_pages_which_have_only_nouns: bool,
+    /// Restrict output to these languages. `None` emits one FST per
+    /// language found in `pages`.
+    pub languages: Option<HashSet<LanguageCode>>,
 }
 
+/// Writes one `<lang>-word-tags.fst` file per language into `out_dir`.
 fn build_fst_from_pages(
     pages: &[PageInfo],
     options: FSTOptions,
-    fst_path: PathBuf,
+    out_dir: PathBuf,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let w = std::io::BufWriter::new(std::fs::File::create(fst_path)?);
-    let mut tb = TagsBuilder::new(w)?;
-
     let exclude_before_checking_empty = if options.exclude_pages_which_have_only_nouns {
         let mut excluded = TagSet::default();
         excluded.insert_tag(&Tag::Noun);
@@ -128,44 +398,81 @@ fn build_fst_from_pages(
         TagSet::default()
     };
 
-    let mut pages_sorted = pages
-        .iter()
-        .filter_map(|info| {
-            if info
-                .tags
+    let mut pages_by_language = HashMap::<LanguageCode, HashMap<String, TagSet>>::new();
+    let mut glosses_by_title = HashMap::<String, Vec<(Tag, Vec<String>)>>::new();
+
+    for info in pages {
+        if !info.glosses.is_empty() {
+            let title = if options.flatten_unicode {
+                unidecode::unidecode(&info.title).to_ascii_lowercase()
+            } else {
+                info.title.to_lowercase()
+            };
+            glosses_by_title
+                .entry(title)
+                .or_insert_with(|| info.glosses.clone());
+        }
+
+        for (lang, tags) in info.tags.iter() {
+            if let Some(languages) = &options.languages {
+                if !languages.contains(lang) {
+                    continue;
+                }
+            }
+
+            if tags
+                .clone()
                 .remove_tag_set(&exclude_before_checking_empty)
                 .is_empty()
             {
                 // filter out words with no tags
-                return None;
+                continue;
             }
 
-            Some((
-                // normalize title
-                if options.flatten_unicode {
-                    unidecode::unidecode(&info.title).to_ascii_lowercase()
-                } else {
-                    info.title.to_lowercase()
-                },
-                info.tags.clone(),
-            ))
-        })
-        // flatten
-        .fold(
-            HashMap::<String, TagSet>::new(),
-            |mut acc, (title, tag_set)| {
-                acc.entry(title).or_default().extend(tag_set);
-                acc
-            },
-        )
-        .into_iter()
-        .collect::<Vec<_>>();
+            let title = if options.flatten_unicode {
+                unidecode::unidecode(&info.title).to_ascii_lowercase()
+            } else {
+                info.title.to_lowercase()
+            };
+
+            pages_by_language
+                .entry(lang.clone())
+                .or_default()
+                .entry(title)
+                .or_default()
+                .extend(tags.clone());
+        }
+    }
+
+    for (lang, words) in pages_by_language {
+        let fst_path = out_dir.join(format!("{}-word-tags.fst", lang));
+        let w = std::io::BufWriter::new(std::fs::File::create(fst_path)?);
+        let mut tb = TagsBuilder::new(w)?;
+
+        let mut words_sorted = words.into_iter().collect::<Vec<_>>();
+        words_sorted.sort_by(|a, b| a.0.cmp(&b.0));
 
-    pages_sorted.sort_by(|a, b| a.0.cmp(&b.0));
+        // Glosses are only captured off English POS templates, so only the
+        // "en" FST packs a gloss-file ordinal into its values.
+        if lang.as_str() == "en" {
+            let gw = std::io::BufWriter::new(std::fs::File::create(
+                out_dir.join("en-word-tags.glosses"),
+            )?);
+            let mut gloss_builder = GlossBuilder::new(gw);
 
-    tb.extend_iter(pages_sorted)?;
+            for (title, tag_set) in words_sorted {
+                let gloss_index = glosses_by_title
+                    .get(&title)
+                    .map(|senses| gloss_builder.push(senses))
+                    .transpose()?;
+                tb.insert_tag_set_with_gloss_index(&title, &tag_set, gloss_index)?;
+            }
+        } else {
+            tb.extend_iter(words_sorted)?;
+        }
 
-    tb.finish()?;
+        tb.finish()?;
+    }
 
     Ok(())
 }
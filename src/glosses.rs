@@ -0,0 +1,176 @@
+//! Sense glosses don't fit an `fst::Map` value, so they're serialized to a
+//! side file and addressed by the ordinal a [`GlossBuilder::push`] call
+//! returns, the same ordinal a [`crate::TagsBuilder`] entry is packed with
+//! via `insert_tag_set_with_gloss_index`. [`GlossLookup`] reads that file
+//! back through an `mmap`, so a multi-gigabyte gloss dump doesn't need to
+//! be loaded into memory up front.
+
+use crate::Tag;
+use std::io::Write;
+
+/// Appends one length-prefixed record per word: a `u32` sense count, then
+/// for each sense a `u8` tag code, a `u32` gloss count, and for each gloss
+/// a `u32` byte length followed by its UTF-8 bytes.
+pub struct GlossBuilder<W: Write> {
+    writer: W,
+    next_index: u32,
+}
+
+// in memory construction
+impl GlossBuilder<Vec<u8>> {
+    pub fn in_memory() -> Self {
+        GlossBuilder {
+            writer: Vec::new(),
+            next_index: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.writer
+    }
+}
+
+impl<W: Write> GlossBuilder<W> {
+    pub fn new(writer: W) -> Self {
+        GlossBuilder {
+            writer,
+            next_index: 0,
+        }
+    }
+
+    /// Appends `senses` as the next record and returns its ordinal.
+    pub fn push(&mut self, senses: &[(Tag, Vec<String>)]) -> std::io::Result<u32> {
+        let index = self.next_index;
+        self.next_index += 1;
+
+        self.writer
+            .write_all(&(senses.len() as u32).to_le_bytes())?;
+        for (tag, glosses) in senses {
+            self.writer.write_all(&[tag.to_code()])?;
+            self.writer
+                .write_all(&(glosses.len() as u32).to_le_bytes())?;
+            for gloss in glosses {
+                self.writer.write_all(&(gloss.len() as u32).to_le_bytes())?;
+                self.writer.write_all(gloss.as_bytes())?;
+            }
+        }
+
+        Ok(index)
+    }
+}
+
+/// Reads back a file written by [`GlossBuilder`], mmap'd so the gloss text
+/// stays resident in the OS page cache rather than the process heap.
+pub struct GlossLookup {
+    mmap: memmap2::Mmap,
+    /// Byte offset of each record, in ordinal order.
+    record_offsets: Vec<usize>,
+}
+
+impl GlossLookup {
+    pub fn open(path: &std::path::Path) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+
+        let mut record_offsets = Vec::new();
+        let mut offset = 0;
+        while offset < mmap.len() {
+            record_offsets.push(offset);
+            offset = skip_record(&mmap, offset);
+        }
+
+        Ok(GlossLookup {
+            mmap,
+            record_offsets,
+        })
+    }
+
+    /// Reads the record at `index`, flattening each tag's sense list into
+    /// one `(Tag, &str)` pair per gloss.
+    pub fn get(&self, index: u32) -> Option<Vec<(Tag, &str)>> {
+        let &offset = self.record_offsets.get(index as usize)?;
+        let mut pos = offset;
+
+        let sense_count = read_u32(&self.mmap, &mut pos);
+        let mut out = Vec::new();
+        for _ in 0..sense_count {
+            let tag = Tag::from_code(self.mmap[pos]);
+            pos += 1;
+            let gloss_count = read_u32(&self.mmap, &mut pos);
+            for _ in 0..gloss_count {
+                let len = read_u32(&self.mmap, &mut pos) as usize;
+                if let Ok(gloss) = std::str::from_utf8(&self.mmap[pos..pos + len]) {
+                    out.push((tag, gloss));
+                }
+                pos += len;
+            }
+        }
+
+        Some(out)
+    }
+}
+
+fn read_u32(data: &[u8], pos: &mut usize) -> u32 {
+    let value = u32::from_le_bytes(data[*pos..*pos + 4].try_into().unwrap());
+    *pos += 4;
+    value
+}
+
+fn skip_record(data: &[u8], mut pos: usize) -> usize {
+    let sense_count = read_u32(data, &mut pos);
+    for _ in 0..sense_count {
+        pos += 1; // tag code
+        let gloss_count = read_u32(data, &mut pos);
+        for _ in 0..gloss_count {
+            let len = read_u32(data, &mut pos) as usize;
+            pos += len;
+        }
+    }
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_multiple_records() {
+        let mut builder = GlossBuilder::in_memory();
+
+        let noun_index = builder
+            .push(&[(Tag::Noun, vec!["a domesticated animal".to_string()])])
+            .unwrap();
+        let verb_index = builder
+            .push(&[(
+                Tag::Verb,
+                vec!["to run quickly".to_string(), "to flee".to_string()],
+            )])
+            .unwrap();
+
+        assert_eq!(noun_index, 0);
+        assert_eq!(verb_index, 1);
+
+        static COUNTER: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+        let unique = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+        let bytes = builder.into_inner();
+        let path = std::env::temp_dir().join(format!(
+            "glosses-test-{}-{}.bin",
+            std::process::id(),
+            unique
+        ));
+        std::fs::write(&path, &bytes).unwrap();
+        let lookup = GlossLookup::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            lookup.get(noun_index),
+            Some(vec![(Tag::Noun, "a domesticated animal")])
+        );
+        assert_eq!(
+            lookup.get(verb_index),
+            Some(vec![(Tag::Verb, "to run quickly"), (Tag::Verb, "to flee")])
+        );
+        assert_eq!(lookup.get(2), None);
+    }
+}
@@ -0,0 +1,204 @@
+//! Rule-based English inflection *generation*, complementing the
+//! recognition side (`ENGLISH_LEMMA_LOOKUP`). Irregular forms harvested
+//! from the dump (`en-irregular plural of`, `en-past of`, `en-comparative
+//! of`) are consulted before any regular rule is applied.
+
+use crate::{InflectionKind, ENGLISH_LEMMA_LOOKUP};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use ustr::{ustr, Ustr};
+
+fn irregular_forms_of(kind: InflectionKind) -> HashMap<Ustr, String> {
+    ENGLISH_LEMMA_LOOKUP
+        .iter()
+        .filter(|(_, _, form_kind)| *form_kind == kind)
+        .map(|(surface_form, lemma, _)| (lemma, surface_form))
+        .collect()
+}
+
+static IRREGULAR_PLURALS: Lazy<HashMap<Ustr, String>> =
+    Lazy::new(|| irregular_forms_of(InflectionKind::IrregularPlural));
+static IRREGULAR_PAST: Lazy<HashMap<Ustr, String>> =
+    Lazy::new(|| irregular_forms_of(InflectionKind::PastTense));
+static IRREGULAR_COMPARATIVE: Lazy<HashMap<Ustr, String>> =
+    Lazy::new(|| irregular_forms_of(InflectionKind::Comparative));
+
+fn is_vowel(c: char) -> bool {
+    matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// True for a CVC-ending word (e.g. "fat", "big") whose final consonant
+/// doubles before a vowel suffix ("fatter", "bigger"), per the usual
+/// English spelling rule. Words ending in w/x/y are excluded ("fix" ->
+/// "fixed", not "fixxed").
+fn doubles_final_consonant(word: &str) -> bool {
+    let chars: Vec<char> = word.chars().collect();
+    chars.len() >= 3
+        && !is_vowel(chars[chars.len() - 3])
+        && is_vowel(chars[chars.len() - 2])
+        && !is_vowel(chars[chars.len() - 1])
+        && !matches!(chars[chars.len() - 1], 'w' | 'x' | 'y')
+}
+
+/// Appends the regular `s`/`es` suffix shared by noun plurals and
+/// third-person-singular verb forms (sibilant clusters and consonant+`y`
+/// both behave the same way for either).
+fn add_s_or_es(word: &str) -> String {
+    let lower = word.to_lowercase();
+    if lower.ends_with(['s', 'x', 'z']) || lower.ends_with("ch") || lower.ends_with("sh") {
+        format!("{}es", word)
+    } else if let Some(stem) = word.strip_suffix('y') {
+        if stem.chars().last().map_or(false, |c| !is_vowel(c)) {
+            format!("{}ies", stem)
+        } else {
+            format!("{}s", word)
+        }
+    } else if let Some(stem) = word.strip_suffix('o') {
+        if stem.chars().last().map_or(false, |c| !is_vowel(c)) {
+            format!("{}es", word)
+        } else {
+            format!("{}s", word)
+        }
+    } else {
+        format!("{}s", word)
+    }
+}
+
+pub fn pluralize(word: &str) -> String {
+    if let Some(irregular) = IRREGULAR_PLURALS.get(&ustr(&word.to_lowercase())) {
+        return irregular.clone();
+    }
+
+    if let Some(stem) = word.strip_suffix("fe") {
+        format!("{}ves", stem)
+    } else if let Some(stem) = word.strip_suffix('f') {
+        format!("{}ves", stem)
+    } else {
+        add_s_or_es(word)
+    }
+}
+
+const SILENT_H_WORDS: &[&str] = &["honor", "honour", "honest", "hour", "heir"];
+const PRONOUNCED_VOWEL_PREFIXES: &[&str] = &["eu", "use", "uni"];
+const PRONOUNCED_VOWEL_WORDS: &[&str] = &["one"];
+
+pub fn indefinite_article(word: &str) -> &'static str {
+    let lower = word.to_lowercase();
+
+    if SILENT_H_WORDS
+        .iter()
+        .any(|silent| lower.starts_with(silent))
+    {
+        return "an";
+    }
+
+    if PRONOUNCED_VOWEL_WORDS.contains(&lower.as_str())
+        || PRONOUNCED_VOWEL_PREFIXES
+            .iter()
+            .any(|prefix| lower.starts_with(prefix))
+    {
+        return "a";
+    }
+
+    match lower.chars().next() {
+        Some(c) if is_vowel(c) => "an",
+        _ => "a",
+    }
+}
+
+/// Adjectives short enough to take `-er`/`-est` instead of `more`/`most`.
+/// This is a length heuristic, not a syllable counter, same spirit as the
+/// rest of the crate's regex-driven parsing.
+fn is_short_adjective(word: &str) -> bool {
+    word.chars().count() <= 6
+}
+
+fn short_form_stem(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix('y') {
+        if stem.chars().last().map_or(false, |c| !is_vowel(c)) {
+            return format!("{}i", stem);
+        }
+    }
+
+    if doubles_final_consonant(word) {
+        let doubled = word.chars().last().unwrap();
+        return format!("{}{}", word, doubled);
+    }
+
+    word.to_string()
+}
+
+pub fn comparative(word: &str) -> String {
+    if let Some(irregular) = IRREGULAR_COMPARATIVE.get(&ustr(&word.to_lowercase())) {
+        return irregular.clone();
+    }
+
+    if is_short_adjective(word) {
+        format!("{}er", short_form_stem(word))
+    } else {
+        format!("more {}", word)
+    }
+}
+
+pub fn superlative(word: &str) -> String {
+    if is_short_adjective(word) {
+        format!("{}est", short_form_stem(word))
+    } else {
+        format!("most {}", word)
+    }
+}
+
+pub fn third_person_singular(word: &str) -> String {
+    add_s_or_es(word)
+}
+
+pub fn past_tense(word: &str) -> String {
+    if let Some(irregular) = IRREGULAR_PAST.get(&ustr(&word.to_lowercase())) {
+        return irregular.clone();
+    }
+
+    if word.ends_with('e') {
+        format!("{}d", word)
+    } else if doubles_final_consonant(word) {
+        format!("{}{}ed", word, word.chars().last().unwrap())
+    } else if let Some(stem) = word.strip_suffix('y') {
+        if stem.chars().last().map_or(false, |c| !is_vowel(c)) {
+            format!("{}ied", stem)
+        } else {
+            format!("{}ed", word)
+        }
+    } else {
+        format!("{}ed", word)
+    }
+}
+
+pub fn present_participle(word: &str) -> String {
+    if word.ends_with('e') && !word.ends_with("ee") {
+        format!("{}ing", &word[..word.len() - 1])
+    } else if doubles_final_consonant(word) {
+        format!("{}{}ing", word, word.chars().last().unwrap())
+    } else {
+        format!("{}ing", word)
+    }
+}
+
+#[test]
+fn regular_rules() {
+    assert_eq!(pluralize("fox"), "foxes");
+    assert_eq!(pluralize("city"), "cities");
+    assert_eq!(pluralize("leaf"), "leaves");
+    assert_eq!(pluralize("cat"), "cats");
+
+    assert_eq!(indefinite_article("apple"), "an");
+    assert_eq!(indefinite_article("honor"), "an");
+    assert_eq!(indefinite_article("unicorn"), "a");
+    assert_eq!(indefinite_article("cat"), "a");
+
+    assert_eq!(comparative("big"), "bigger");
+    assert_eq!(comparative("happy"), "happier");
+    assert_eq!(comparative("beautiful"), "more beautiful");
+
+    assert_eq!(past_tense("hope"), "hoped");
+    assert_eq!(past_tense("stop"), "stopped");
+    assert_eq!(present_participle("run"), "running");
+}
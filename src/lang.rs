@@ -0,0 +1,72 @@
+use oxilangtag::LanguageTag;
+
+/// A validated BCP-47 language tag (RFC 5646), e.g. `en`, `fr`, `zh-Hant`.
+///
+/// Wraps `oxilangtag::LanguageTag` so the rest of the crate has a single,
+/// hashable, orderable type to key per-language data structures by.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LanguageCode(LanguageTag<String>);
+
+impl LanguageCode {
+    /// Parses and lowercases a BCP-47 language tag. Note this rejects fewer
+    /// inputs than it might look like it should: the well-formed `en`
+    /// prefix of a garbage `{{en-verb))` template parses fine, since
+    /// `oxilangtag::LanguageTag::parse` only validates tag *structure*, not
+    /// whether the tag denotes a real language. Callers that need to reject
+    /// a malformed suffix (like `parse::resolve_tag`) do so separately,
+    /// against their own alias table.
+    pub fn parse(tag: &str) -> Result<Self, String> {
+        LanguageTag::parse(tag.to_ascii_lowercase())
+            .map(LanguageCode)
+            .map_err(|err| format!("Invalid language tag {:?}: {:?}", tag, err))
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// The primary language subtag, e.g. `"en"` for `en` and for `en-GB`.
+    pub fn primary_language(&self) -> &str {
+        self.0.primary_language()
+    }
+}
+
+impl std::fmt::Display for LanguageCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_lowercases_valid_tags() {
+        assert_eq!(LanguageCode::parse("en").unwrap().as_str(), "en");
+        assert_eq!(LanguageCode::parse("FR").unwrap().as_str(), "fr");
+        assert_eq!(LanguageCode::parse("zh-Hant").unwrap().as_str(), "zh-hant");
+    }
+
+    #[test]
+    fn primary_language_ignores_subtags() {
+        let lang = LanguageCode::parse("en-GB").unwrap();
+        assert_eq!(lang.primary_language(), "en");
+    }
+
+    #[test]
+    fn rejects_tags_with_invalid_characters() {
+        assert!(LanguageCode::parse("en_GB").is_err());
+        assert!(LanguageCode::parse("").is_err());
+    }
+
+    #[test]
+    fn parses_short_garbage_that_alias_lookups_reject_separately() {
+        // `tag_regex` in `parse.rs` only ever captures a 2-3 letter prefix as
+        // `lang`, so by the time it reaches here a leftover `))` from an
+        // unterminated template like `{{en-verb))` has already been cut off.
+        // `LanguageCode::parse` has no way to know "verb))" was ever there,
+        // and happily accepts the well-formed "en" it was actually given.
+        assert!(LanguageCode::parse("en").is_ok());
+    }
+}
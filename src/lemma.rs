@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+use ustr::Ustr;
+
+/// The relationship an inflected surface form has to its lemma, e.g.
+/// `"ran"` is the `PastTense` of `"run"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InflectionKind {
+    PastTense,
+    ThirdPersonSingular,
+    Comparative,
+    Superlative,
+    IrregularPlural,
+}
+
+impl InflectionKind {
+    fn to_code(self) -> u8 {
+        match self {
+            InflectionKind::PastTense => 0,
+            InflectionKind::ThirdPersonSingular => 1,
+            InflectionKind::Comparative => 2,
+            InflectionKind::Superlative => 3,
+            InflectionKind::IrregularPlural => 4,
+        }
+    }
+
+    fn from_code(code: u8) -> Self {
+        match code {
+            0 => InflectionKind::PastTense,
+            1 => InflectionKind::ThirdPersonSingular,
+            2 => InflectionKind::Comparative,
+            3 => InflectionKind::Superlative,
+            4 => InflectionKind::IrregularPlural,
+            other => panic!("Invalid InflectionKind code {}", other),
+        }
+    }
+}
+
+/// FST values only hold a `u64`, so a surface form's candidate lemmas are
+/// stored out-of-line: the value is an index into a parallel `relations`
+/// table, each entry a small `Vec` of `(lemma_index, InflectionKind)`
+/// pairs. More than one entry means the surface form was attested with
+/// more than one relationship while building, e.g. a page titled
+/// `"leaves"` carrying both `{{en-irregular plural of|leaf}}` and
+/// `{{en-third-person singular of|leave}}`. `lemma_index` in turn indexes
+/// the `lemmas` string table.
+pub struct LemmaBuilder<W: std::io::Write> {
+    map_builder: fst::MapBuilder<W>,
+    lemmas: Vec<Ustr>,
+    lemma_indices: HashMap<Ustr, u32>,
+    relations: Vec<Vec<(u32, InflectionKind)>>,
+}
+
+// in memory construction
+impl LemmaBuilder<Vec<u8>> {
+    pub fn in_memory() -> Self {
+        LemmaBuilder {
+            map_builder: fst::MapBuilder::memory(),
+            lemmas: Vec::new(),
+            lemma_indices: HashMap::new(),
+            relations: Vec::new(),
+        }
+    }
+}
+
+impl<W: std::io::Write> LemmaBuilder<W> {
+    pub fn new(writer: W) -> Result<Self, fst::Error> {
+        Ok(LemmaBuilder {
+            map_builder: fst::MapBuilder::new(writer)?,
+            lemmas: Vec::new(),
+            lemma_indices: HashMap::new(),
+            relations: Vec::new(),
+        })
+    }
+
+    fn lemma_index(&mut self, lemma: Ustr) -> u32 {
+        if let Some(&index) = self.lemma_indices.get(&lemma) {
+            index
+        } else {
+            let index = self.lemmas.len() as u32;
+            self.lemmas.push(lemma);
+            self.lemma_indices.insert(lemma, index);
+            index
+        }
+    }
+
+    /// Inserts `surface_form -> relations`, one `(lemma, kind)` pair per
+    /// relationship the surface form was attested with. Keys must be
+    /// inserted in lexicographic order, same as `TagsBuilder`.
+    pub fn insert(
+        &mut self,
+        surface_form: &str,
+        relations: &[(Ustr, InflectionKind)],
+    ) -> Result<(), String> {
+        let encoded = relations
+            .iter()
+            .map(|&(lemma, kind)| (self.lemma_index(lemma), kind))
+            .collect();
+
+        let group_index = self.relations.len() as u64;
+        self.relations.push(encoded);
+
+        self.map_builder
+            .insert(surface_form, group_index)
+            .map_err(|err| {
+                format!(
+                    "Expected to insert key ({:?}), but got error:\n{:#?}",
+                    surface_form, err
+                )
+            })
+    }
+
+    /// Finishes the FST and returns the lemma string table and the
+    /// relations table, in the order referenced by the encoded indices,
+    /// for the caller to persist alongside it. See [`serialize_relations`].
+    pub fn finish(self) -> Result<(Vec<Ustr>, Vec<Vec<(u32, InflectionKind)>>), fst::Error> {
+        self.map_builder.finish()?;
+        Ok((self.lemmas, self.relations))
+    }
+}
+
+/// Serializes a [`LemmaBuilder::finish`] relations table for the caller to
+/// persist, e.g. to `en-lemmas-relations.bin`. [`deserialize_relations`]
+/// reads it back. One length-prefixed record per group: a `u32` relation
+/// count, then for each relation a `u32` lemma index and a `u8` kind code.
+pub fn serialize_relations(relations: &[Vec<(u32, InflectionKind)>]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for group in relations {
+        out.extend_from_slice(&(group.len() as u32).to_le_bytes());
+        for &(lemma_index, kind) in group {
+            out.extend_from_slice(&lemma_index.to_le_bytes());
+            out.push(kind.to_code());
+        }
+    }
+    out
+}
+
+/// Reads back a relations table written by [`serialize_relations`].
+pub fn deserialize_relations(data: &[u8]) -> Vec<Vec<(u32, InflectionKind)>> {
+    let mut groups = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let count = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+        pos += 4;
+
+        let mut group = Vec::with_capacity(count);
+        for _ in 0..count {
+            let lemma_index = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap());
+            pos += 4;
+            let kind = InflectionKind::from_code(data[pos]);
+            pos += 1;
+            group.push((lemma_index, kind));
+        }
+        groups.push(group);
+    }
+    groups
+}
+
+pub struct LemmaLookup<D> {
+    map: fst::Map<D>,
+    lemmas: Vec<Ustr>,
+    relations: Vec<Vec<(u32, InflectionKind)>>,
+}
+
+impl<D: AsRef<[u8]>> LemmaLookup<D> {
+    pub fn new(
+        map_data: D,
+        lemmas: Vec<Ustr>,
+        relations: Vec<Vec<(u32, InflectionKind)>>,
+    ) -> Result<Self, String> {
+        fst::Map::new(map_data)
+            .map(|map| LemmaLookup {
+                map,
+                lemmas,
+                relations,
+            })
+            .map_err(|fst_err| format!("Invalid LemmaLookup: {:?}", fst_err))
+    }
+
+    /// Resolves an inflected surface form to every lemma it was attested
+    /// as an inflection of, and the relationship each has to it, e.g.
+    /// `resolve("leaves") == Some(vec![("leaf", IrregularPlural), ("leave",
+    /// ThirdPersonSingular)])` when a page carried more than one
+    /// inflection template.
+    pub fn resolve(&self, surface_form: &str) -> Option<Vec<(Ustr, InflectionKind)>> {
+        let group_index = self.map.get(surface_form)? as usize;
+        let group = self.relations.get(group_index)?;
+        Some(
+            group
+                .iter()
+                .filter_map(|&(lemma_index, kind)| {
+                    self.lemmas
+                        .get(lemma_index as usize)
+                        .copied()
+                        .map(|lemma| (lemma, kind))
+                })
+                .collect(),
+        )
+    }
+
+    /// Iterates every `(surface_form, lemma, kind)` triple, one per
+    /// relationship, e.g. for building a reverse lemma-to-form index.
+    pub fn iter(&self) -> impl Iterator<Item = (String, Ustr, InflectionKind)> + '_ {
+        use fst::Streamer;
+
+        let mut stream = self.map.stream();
+        let mut pending: Vec<(String, Ustr, InflectionKind)> = Vec::new();
+        std::iter::from_fn(move || loop {
+            if let Some(item) = pending.pop() {
+                return Some(item);
+            }
+
+            let (surface_form, group_index) = stream.next()?;
+            let surface_form = String::from_utf8_lossy(surface_form).into_owned();
+            let Some(group) = self.relations.get(group_index as usize) else {
+                continue;
+            };
+
+            pending.extend(group.iter().filter_map(|&(lemma_index, kind)| {
+                self.lemmas
+                    .get(lemma_index as usize)
+                    .copied()
+                    .map(|lemma| (surface_form.clone(), lemma, kind))
+            }));
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ustr::ustr;
+
+    #[test]
+    fn round_trips_surface_forms_to_lemmas() {
+        // Keys must be inserted in lexicographic order, same as TagsBuilder.
+        let mut builder = LemmaBuilder::in_memory();
+        builder
+            .insert("mice", &[(ustr("mouse"), InflectionKind::IrregularPlural)])
+            .unwrap();
+        builder
+            .insert("ran", &[(ustr("run"), InflectionKind::PastTense)])
+            .unwrap();
+        builder
+            .insert(
+                "runs",
+                &[(ustr("run"), InflectionKind::ThirdPersonSingular)],
+            )
+            .unwrap();
+
+        let LemmaBuilder {
+            map_builder,
+            lemmas,
+            relations,
+            ..
+        } = builder;
+        let map_data = map_builder.into_inner().unwrap();
+        let lookup = LemmaLookup::new(map_data, lemmas, relations).unwrap();
+
+        assert_eq!(
+            lookup.resolve("ran"),
+            Some(vec![(ustr("run"), InflectionKind::PastTense)])
+        );
+        assert_eq!(
+            lookup.resolve("runs"),
+            Some(vec![(ustr("run"), InflectionKind::ThirdPersonSingular)])
+        );
+        assert_eq!(
+            lookup.resolve("mice"),
+            Some(vec![(ustr("mouse"), InflectionKind::IrregularPlural)])
+        );
+        assert_eq!(lookup.resolve("geese"), None);
+    }
+
+    #[test]
+    fn resolves_every_inflection_when_a_page_has_more_than_one() {
+        // e.g. a page titled "leaves" carrying both
+        // `{{en-irregular plural of|leaf}}` and `{{en-third-person singular of|leave}}`.
+        let mut builder = LemmaBuilder::in_memory();
+        builder
+            .insert(
+                "leaves",
+                &[
+                    (ustr("leaf"), InflectionKind::IrregularPlural),
+                    (ustr("leave"), InflectionKind::ThirdPersonSingular),
+                ],
+            )
+            .unwrap();
+
+        let LemmaBuilder {
+            map_builder,
+            lemmas,
+            relations,
+            ..
+        } = builder;
+        let map_data = map_builder.into_inner().unwrap();
+        let lookup = LemmaLookup::new(map_data, lemmas, relations).unwrap();
+
+        assert_eq!(
+            lookup.resolve("leaves"),
+            Some(vec![
+                (ustr("leaf"), InflectionKind::IrregularPlural),
+                (ustr("leave"), InflectionKind::ThirdPersonSingular),
+            ])
+        );
+    }
+
+    #[test]
+    fn relations_round_trip_through_serialization() {
+        let relations = vec![
+            vec![(0, InflectionKind::IrregularPlural)],
+            vec![
+                (0, InflectionKind::IrregularPlural),
+                (1, InflectionKind::ThirdPersonSingular),
+            ],
+        ];
+
+        assert_eq!(
+            deserialize_relations(&serialize_relations(&relations)),
+            relations
+        );
+    }
+}
@@ -12,14 +12,39 @@
 //! assert_eq!(Some(TagSet::of(&[Tag::Noun, Tag::Verb])), ENGLISH_TAG_LOOKUP.get("harbor"));
 //! ```
 
+mod glosses;
+pub mod inflect;
+mod lang;
+mod lemma;
 mod tags;
+mod translate;
 
 use once_cell::sync::Lazy;
+use ustr::ustr;
 
 pub use fst::Map;
-pub use tags::{Tag, TagSet, TagsBuilder, TagsLookup};
+pub use glosses::{GlossBuilder, GlossLookup};
+pub use lang::LanguageCode;
+pub use lemma::{serialize_relations, InflectionKind, LemmaBuilder, LemmaLookup};
+pub use tags::{Tag, TagSet, TagsBuilder, TagsLookup, TagsLookupRegistry};
+pub use translate::{TranslationBuilder, TranslationLookup};
 
 pub static ENGLISH_TAG_LOOKUP: Lazy<TagsLookup<&[u8]>> = Lazy::new(|| {
-    tags::TagsLookup::new(include_bytes!("../dist/english-word-tags.fst").as_ref())
+    tags::TagsLookup::new(include_bytes!("../dist/en-word-tags.fst").as_ref())
         .expect("File was not found")
 });
+
+pub static ENGLISH_LEMMA_LOOKUP: Lazy<LemmaLookup<&[u8]>> = Lazy::new(|| {
+    let lemmas = include_str!("../dist/en-lemmas.txt")
+        .lines()
+        .map(ustr)
+        .collect();
+    let relations = lemma::deserialize_relations(include_bytes!("../dist/en-lemmas-relations.bin"));
+
+    LemmaLookup::new(
+        include_bytes!("../dist/en-lemmas.fst").as_ref(),
+        lemmas,
+        relations,
+    )
+    .expect("File was not found")
+});
@@ -75,6 +75,20 @@ impl TagSet {
     }
 }
 
+/// FST values only hold a `u64`, so a gloss-file ordinal is packed into the
+/// high bits alongside the `TagSet` mask in the low 16 bits. `0` means
+/// "no glosses recorded", so real ordinals are stored 1-based.
+fn encode_tag_value(mask: u32, gloss_index: Option<u32>) -> u64 {
+    let gloss_index = gloss_index.map_or(0, |index| index + 1);
+    ((gloss_index as u64) << 16) | mask as u64
+}
+
+fn decode_tag_value(value: u64) -> (u32, Option<u32>) {
+    let mask = (value & 0xffff) as u32;
+    let gloss_index = (value >> 16) as u32;
+    (mask, (gloss_index > 0).then(|| gloss_index - 1))
+}
+
 pub struct TagsBuilder<W: std::io::Write>(fst::MapBuilder<W>);
 
 // in memory construction
@@ -114,6 +128,25 @@ impl<W: std::io::Write> TagsBuilder<W> {
         })
     }
 
+    /// Like [`Self::insert_tag_set`], but also packs in the ordinal a
+    /// [`crate::glosses::GlossBuilder::push`] call returned for `key`, if
+    /// any senses were recorded for it.
+    pub fn insert_tag_set_with_gloss_index(
+        &mut self,
+        key: &str,
+        tag_set: &TagSet,
+        gloss_index: Option<u32>,
+    ) -> Result<(), String> {
+        self.0
+            .insert(key, encode_tag_value(tag_set.to_mask(), gloss_index))
+            .map_err(|err| {
+                format!(
+                    "Expected to insert key ({:?}) with tags ({:?}), but got error:\n{:#?}",
+                    key, tag_set, err
+                )
+            })
+    }
+
     pub fn extend_iter<I: IntoIterator<Item = (String, TagSet)>>(
         &mut self,
         iter: I,
@@ -131,17 +164,88 @@ impl<W: std::io::Write> TagsBuilder<W> {
     }
 }
 
-pub struct TagsLookup<D>(fst::Map<D>);
+pub struct TagsLookup<D> {
+    map: fst::Map<D>,
+    glosses: Option<crate::glosses::GlossLookup>,
+}
 
 impl<D: AsRef<[u8]>> TagsLookup<D> {
     pub fn new(data: D) -> Result<Self, String> {
         fst::Map::new(data)
-            .map(TagsLookup)
+            .map(|map| TagsLookup { map, glosses: None })
             .map_err(|fst_err| format!("Invalid TagsLookup: {:?}", fst_err))
     }
 
+    /// Attaches a gloss side file produced by [`crate::glosses::GlossBuilder`],
+    /// mmap'd so its contents don't need to be loaded into memory up front.
+    pub fn with_glosses(mut self, glosses_path: &std::path::Path) -> std::io::Result<Self> {
+        self.glosses = Some(crate::glosses::GlossLookup::open(glosses_path)?);
+        Ok(self)
+    }
+
     pub fn get(&self, key: &str) -> Option<TagSet> {
-        self.0.get(key).map(|mask| TagSet::from_mask(mask as u32))
+        self.map
+            .get(key)
+            .map(|value| TagSet::from_mask(decode_tag_value(value).0))
+    }
+
+    /// Looks up the sense glosses captured for `key`, if any were recorded
+    /// and a gloss side file has been attached via [`Self::with_glosses`].
+    pub fn glosses(&self, key: &str) -> Option<Vec<(Tag, &str)>> {
+        let (_, gloss_index) = decode_tag_value(self.map.get(key)?);
+        self.glosses.as_ref()?.get(gloss_index?)
+    }
+}
+
+/// A [`TagsLookup`] per language, so a single multilingual dump can be
+/// served from one value instead of one `ENGLISH_TAG_LOOKUP`-style static
+/// per language.
+pub struct TagsLookupRegistry<D>(std::collections::HashMap<crate::LanguageCode, TagsLookup<D>>);
+
+impl<D: AsRef<[u8]>> TagsLookupRegistry<D> {
+    pub fn new(
+        per_language: std::collections::HashMap<crate::LanguageCode, D>,
+    ) -> Result<Self, String> {
+        per_language
+            .into_iter()
+            .map(|(lang, data)| TagsLookup::new(data).map(|lookup| (lang, lookup)))
+            .collect::<Result<_, _>>()
+            .map(TagsLookupRegistry)
+    }
+
+    pub fn get(&self, lang: &str, key: &str) -> Option<TagSet> {
+        let lang = crate::LanguageCode::parse(lang).ok()?;
+        self.0.get(&lang)?.get(key)
+    }
+}
+
+impl TagsLookupRegistry<Vec<u8>> {
+    /// Loads every `<lang>-word-tags.fst` file in `dir` into the registry,
+    /// keyed by the language code in its filename.
+    pub fn open_dir(dir: &std::path::Path) -> std::io::Result<Self> {
+        let mut per_language = std::collections::HashMap::new();
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("fst") {
+                continue;
+            }
+            let Some(lang_str) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.strip_suffix("-word-tags"))
+            else {
+                continue;
+            };
+            let Ok(lang) = crate::LanguageCode::parse(lang_str) else {
+                continue;
+            };
+
+            per_language.insert(lang, std::fs::read(&path)?);
+        }
+
+        TagsLookupRegistry::new(per_language)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
     }
 }
 
@@ -194,6 +298,16 @@ impl Tag {
         }
     }
 
+    /// A compact one-byte encoding for the gloss side file: the bit index
+    /// of the tag's [`Tag::to_mask`] bit, which [`Tag::from_code`] maps back.
+    pub(crate) fn to_code(self) -> u8 {
+        self.to_mask().trailing_zeros() as u8
+    }
+
+    pub(crate) fn from_code(code: u8) -> Self {
+        Self::from_u32(code as u32)
+    }
+
     fn from_u32(i: u32) -> Self {
         match i {
             1 => Tag::Adjective,
@@ -223,3 +337,31 @@ fn tags() {
         vec![Tag::Determiner, Tag::Particle]
     );
 }
+
+#[test]
+fn tags_lookup_registry_dispatches_by_language() {
+    let mut en_builder = TagsBuilder::in_memory();
+    en_builder.insert_tag("harbor", &Tag::Noun);
+    let en_data = en_builder.into_inner();
+
+    let mut fr_builder = TagsBuilder::in_memory();
+    fr_builder.insert_tag("port", &Tag::Noun);
+    let fr_data = fr_builder.into_inner();
+
+    let mut per_language = std::collections::HashMap::new();
+    per_language.insert(crate::LanguageCode::parse("en").unwrap(), en_data);
+    per_language.insert(crate::LanguageCode::parse("fr").unwrap(), fr_data);
+
+    let registry = TagsLookupRegistry::new(per_language).unwrap();
+
+    assert_eq!(
+        registry.get("en", "harbor"),
+        Some(TagSet::of([Tag::Noun].iter()))
+    );
+    assert_eq!(
+        registry.get("fr", "port"),
+        Some(TagSet::of([Tag::Noun].iter()))
+    );
+    assert_eq!(registry.get("en", "port"), None);
+    assert_eq!(registry.get("de", "port"), None);
+}
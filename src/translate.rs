@@ -0,0 +1,91 @@
+/// Builds an `fst::Map` from English headword to one or more foreign-term
+/// translations into a single target language. Like [`crate::LemmaBuilder`],
+/// the FST value is only a `u64`, so the terms are stored out-of-line in a
+/// parallel string table, one entry per headword, joined with `"; "` when a
+/// headword has more than one attested translation.
+pub struct TranslationBuilder<W: std::io::Write> {
+    map_builder: fst::MapBuilder<W>,
+    terms: Vec<String>,
+}
+
+// in memory construction
+impl TranslationBuilder<Vec<u8>> {
+    pub fn in_memory() -> Self {
+        TranslationBuilder {
+            map_builder: fst::MapBuilder::memory(),
+            terms: Vec::new(),
+        }
+    }
+}
+
+impl<W: std::io::Write> TranslationBuilder<W> {
+    pub fn new(writer: W) -> Result<Self, fst::Error> {
+        Ok(TranslationBuilder {
+            map_builder: fst::MapBuilder::new(writer)?,
+            terms: Vec::new(),
+        })
+    }
+
+    /// Inserts `headword -> foreign_terms`. Keys must be inserted in
+    /// lexicographic order, same as `TagsBuilder`.
+    pub fn insert(&mut self, headword: &str, foreign_terms: &[String]) -> Result<(), String> {
+        let index = self.terms.len() as u64;
+        self.terms.push(foreign_terms.join("; "));
+
+        self.map_builder.insert(headword, index).map_err(|err| {
+            format!(
+                "Expected to insert key ({:?}), but got error:\n{:#?}",
+                headword, err
+            )
+        })
+    }
+
+    /// Finishes the FST and returns the term table, in the order referenced
+    /// by the encoded indices, for the caller to persist alongside it.
+    pub fn finish(self) -> Result<Vec<String>, fst::Error> {
+        self.map_builder.finish()?;
+        Ok(self.terms)
+    }
+}
+
+pub struct TranslationLookup<D> {
+    map: fst::Map<D>,
+    terms: Vec<String>,
+}
+
+impl<D: AsRef<[u8]>> TranslationLookup<D> {
+    pub fn new(map_data: D, terms: Vec<String>) -> Result<Self, String> {
+        fst::Map::new(map_data)
+            .map(|map| TranslationLookup { map, terms })
+            .map_err(|fst_err| format!("Invalid TranslationLookup: {:?}", fst_err))
+    }
+
+    /// Looks up the foreign-term translation(s) of `headword`, `"; "`-joined
+    /// when more than one was attested.
+    pub fn get(&self, headword: &str) -> Option<&str> {
+        let index = self.map.get(headword)? as usize;
+        self.terms.get(index).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_single_and_multiple_terms() {
+        let mut builder = TranslationBuilder::in_memory();
+        builder.insert("bank", &["banco".to_string()]).unwrap();
+        builder
+            .insert("dog", &["perro".to_string(), "can".to_string()])
+            .unwrap();
+
+        let TranslationBuilder { map_builder, terms } = builder;
+        let map_data = map_builder.into_inner().unwrap();
+
+        let lookup = TranslationLookup::new(map_data, terms).unwrap();
+        assert_eq!(lookup.get("bank"), Some("banco"));
+        assert_eq!(lookup.get("dog"), Some("perro; can"));
+        assert_eq!(lookup.get("cat"), None);
+    }
+}